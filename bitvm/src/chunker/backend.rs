@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+
+use super::common;
+
+/// Describes the proof system a [`Segment`](super::segment::Segment) is chunking.
+///
+/// [`Segment::script`](super::segment::Segment::script) used to special-case
+/// proof data by string membership in [`common::PROOF_NAMES`], hardcoding the
+/// bn254 element set (`Fq6Type`, `G1PointType`, `G2PointType`). A `ProofBackend`
+/// owns the set of proof-witness identifiers instead, so the same segment
+/// machinery can chunk verifiers over other pairing curves or proof systems
+/// without touching `Segment::script`. The "move the original data vs. compare
+/// the hash" decision in `script()` is driven by [`ProofBackend::is_proof_element`].
+pub trait ProofBackend: Debug + Send + Sync {
+    /// Identifiers of the proof-witness elements. These are the elements whose
+    /// data is moved in full when verifying the proof, rather than being
+    /// reduced to their BLAKE3 commitment.
+    fn proof_names(&self) -> &[&'static str];
+
+    /// Whether `id` names a proof-witness element of this backend.
+    fn is_proof_element(&self, id: &str) -> bool {
+        self.proof_names().contains(&id)
+    }
+}
+
+/// The default backend: bn254 Groth16 with `Fq6` field elements and `G1`/`G2`
+/// group points, as named in [`common::PROOF_NAMES`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bn254Backend;
+
+impl ProofBackend for Bn254Backend {
+    fn proof_names(&self) -> &[&'static str] {
+        &common::PROOF_NAMES
+    }
+}
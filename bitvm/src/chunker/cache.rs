@@ -0,0 +1,268 @@
+use std::io::{Read, Write};
+use std::path::Path;
+
+use bitcoin::ScriptBuf;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use super::backend::ProofBackend;
+use super::common::{RawWitness, BLAKE3_HASH_LENGTH};
+use super::elements::ElementTrait;
+use super::segment::Segment;
+use crate::bn254::utils::Hint;
+use crate::treepp::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Chunking is expensive and fully deterministic, yet nothing persists the
+/// result: every run recomputes [`Segment::script`]/[`Segment::witness`] from
+/// scratch. This module serializes a compiled segment list to a compact on-disk
+/// archive and reloads it.
+///
+/// [`Script`] is not serde-friendly, so the *compiled* bytecode is stored rather
+/// than the structured script, and the element trait objects are flattened to
+/// their identity/metadata ([`ElementMeta`]) instead of the `dyn ElementTrait`
+/// themselves. The archive is encoded with MessagePack and wrapped in DEFLATE so
+/// the on-disk form of a large program stays compact.
+
+/// Identity and metadata of one element of a segment's parameter or result list.
+///
+/// This is everything [`Segment::script`] consults about an element: its `id`
+/// (for bit-commitment lookup and proof-element membership) and its
+/// `witness_size`. `is_proof` caches the segment backend's
+/// [`is_proof_element`](super::backend::ProofBackend::is_proof_element) decision
+/// at serialization time so a reloaded segment does not depend on the original
+/// backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementMeta {
+    pub id: String,
+    pub witness_size: usize,
+    pub is_proof: bool,
+}
+
+impl ElementMeta {
+    fn from_element(element: &Arc<Box<dyn ElementTrait>>, backend: &dyn ProofBackend) -> Self {
+        Self {
+            id: element.id().to_string(),
+            witness_size: element.as_ref().witness_size(),
+            is_proof: backend.is_proof_element(element.id()),
+        }
+    }
+}
+
+/// A flattened, serde-friendly view of a [`Segment`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializableSegment {
+    pub name: String,
+    /// Compiled bytecode of the segment's inner script (`script.compile()`).
+    pub script: Vec<u8>,
+    pub parameter_list: Vec<ElementMeta>,
+    pub result_list: Vec<ElementMeta>,
+    pub hints: Vec<Hint>,
+    pub final_segment: bool,
+}
+
+impl SerializableSegment {
+    pub fn from_segment(segment: &Segment) -> Self {
+        Self {
+            name: segment.name.clone(),
+            script: segment.script.clone().compile().to_bytes(),
+            parameter_list: segment
+                .parameter_list
+                .iter()
+                .map(|element| ElementMeta::from_element(element, &*segment.backend))
+                .collect(),
+            result_list: segment
+                .result_list
+                .iter()
+                .map(|element| ElementMeta::from_element(element, &*segment.backend))
+                .collect(),
+            hints: segment.hints.clone(),
+            final_segment: segment.final_segment,
+        }
+    }
+
+    /// Reconstruct a [`Segment`] whose `script` field is the precompiled
+    /// bytecode, ready to be spliced into [`Segment::script`], and whose element
+    /// lists are [`CachedElement`] stand-ins carrying the persisted metadata.
+    ///
+    /// The reloaded segment is **script-only**: [`CachedElement`] carries no
+    /// field/group value, so [`CachedElement::to_witness`] returns `None` and
+    /// calling [`Segment::witness`] on a result that has any parameter panics.
+    /// A cache round-trip
+    /// reproduces `script()` (the on-chain taproot branch), not `witness()`,
+    /// which must be rebuilt from the live assigner.
+    ///
+    /// The proof-witness decision is preserved exactly: instead of defaulting to
+    /// [`Bn254Backend`](super::backend::Bn254Backend) — which would re-consult
+    /// the global `PROOF_NAMES` and silently mislabel a segment chunked with a
+    /// non-bn254 backend — the segment is given a [`CachedBackend`] that answers
+    /// `is_proof_element` from the persisted per-element [`ElementMeta::is_proof`]
+    /// flags.
+    pub fn into_segment(self) -> Segment {
+        let script = script! {}.push_script(ScriptBuf::from_bytes(self.script));
+        let backend: Arc<dyn ProofBackend> = Arc::new(CachedBackend::from_metas(
+            self.parameter_list.iter().chain(self.result_list.iter()),
+        ));
+        let parameter_list = self
+            .parameter_list
+            .into_iter()
+            .map(CachedElement::into_dyn)
+            .collect();
+        let result_list = self
+            .result_list
+            .into_iter()
+            .map(CachedElement::into_dyn)
+            .collect();
+
+        Segment {
+            name: self.name,
+            script,
+            parameter_list,
+            result_list,
+            hints: self.hints,
+            final_segment: self.final_segment,
+            backend,
+        }
+    }
+}
+
+/// A [`ProofBackend`] recovered from a cached segment.
+///
+/// Rather than defaulting to a fixed curve, it remembers the identifiers that
+/// were proof-witness elements at serialization time (the persisted
+/// [`ElementMeta::is_proof`] flags) and answers `is_proof_element` from that
+/// set, so a segment chunked with any backend reloads with the same
+/// move-data-vs-compare-hash behaviour.
+#[derive(Debug, Clone)]
+pub struct CachedBackend {
+    proof_ids: HashSet<String>,
+}
+
+impl CachedBackend {
+    fn from_metas<'a>(metas: impl Iterator<Item = &'a ElementMeta>) -> Self {
+        Self {
+            proof_ids: metas
+                .filter(|meta| meta.is_proof)
+                .map(|meta| meta.id.clone())
+                .collect(),
+        }
+    }
+}
+
+impl ProofBackend for CachedBackend {
+    fn proof_names(&self) -> &[&'static str] {
+        // The persisted identifiers are owned `String`s, so there are no
+        // `'static` names to hand back; membership is answered directly below.
+        &[]
+    }
+
+    fn is_proof_element(&self, id: &str) -> bool {
+        self.proof_ids.contains(id)
+    }
+}
+
+/// A metadata-only [`ElementTrait`] recovered from a [`SerializableSegment`].
+///
+/// It carries no field/group value — only the identity and sizes the segment
+/// machinery consults — so it can answer `id`/`witness_size` for a reloaded
+/// program without reconstructing the original bn254 element.
+#[derive(Debug, Clone)]
+pub struct CachedElement {
+    meta: ElementMeta,
+}
+
+impl CachedElement {
+    fn into_dyn(meta: ElementMeta) -> Arc<Box<dyn ElementTrait>> {
+        Arc::new(Box::new(Self { meta }))
+    }
+}
+
+impl ElementTrait for CachedElement {
+    fn id(&self) -> &str {
+        &self.meta.id
+    }
+
+    fn witness_size(&self) -> usize {
+        self.meta.witness_size
+    }
+
+    fn to_witness(&self) -> Option<RawWitness> {
+        None
+    }
+
+    fn to_hash_witness(&self) -> Option<RawWitness> {
+        None
+    }
+}
+
+/// A whole segment list plus a format tag, as it lives on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentArchive {
+    /// Length of the BLAKE3 hash the program was compiled against, so a reload
+    /// against a mismatching build fails loudly instead of producing garbage.
+    pub hash_length: usize,
+    pub segments: Vec<SerializableSegment>,
+}
+
+impl SegmentArchive {
+    pub fn from_segments(segments: &[Segment]) -> Self {
+        Self {
+            hash_length: BLAKE3_HASH_LENGTH,
+            segments: segments
+                .iter()
+                .map(SerializableSegment::from_segment)
+                .collect(),
+        }
+    }
+
+    pub fn into_segments(self) -> Vec<Segment> {
+        self.segments
+            .into_iter()
+            .map(SerializableSegment::into_segment)
+            .collect()
+    }
+
+    /// Encode as DEFLATE-wrapped MessagePack.
+    pub fn to_bytes(&self) -> std::io::Result<Vec<u8>> {
+        let packed = rmp_serde::to_vec(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&packed)?;
+        encoder.finish()
+    }
+
+    /// Decode a DEFLATE-wrapped MessagePack archive.
+    ///
+    /// Fails loudly if the archive was built against a different
+    /// [`BLAKE3_HASH_LENGTH`] than the current build: the compiled bytecode
+    /// hardcodes that length, so reloading across a mismatch would splice
+    /// scripts that no longer line up with the program's commitments.
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut decoder = DeflateDecoder::new(bytes);
+        let mut packed = Vec::new();
+        decoder.read_to_end(&mut packed)?;
+        let archive: Self = rmp_serde::from_slice(&packed)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if archive.hash_length != BLAKE3_HASH_LENGTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "segment archive hash length {} does not match build ({})",
+                    archive.hash_length, BLAKE3_HASH_LENGTH
+                ),
+            ));
+        }
+        Ok(archive)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes()?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
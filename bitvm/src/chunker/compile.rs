@@ -0,0 +1,55 @@
+use super::assigner::BCAssigner;
+use super::common::RawWitness;
+use super::segment::Segment;
+use crate::treepp::*;
+
+/// Batch-compile a slice of [`Segment`]s into their `(script, witness)` pairs.
+///
+/// Building the taproot of a real disprove program means calling
+/// [`Segment::script`] and [`Segment::witness`] for every branch, and every
+/// `witness` call runs a full `execute_script` pass over the hints; with
+/// thousands of segments this is an O(n) sequential grind. [`compile_segments`] partitions the slice across a
+/// fixed-size worker pool, compiles each partition independently and reassembles
+/// the results in input order, so the output is identical to the single-threaded
+/// path regardless of `threads`.
+///
+/// `threads` is the pool size; `1` (or an empty slice) takes the plain
+/// sequential path. Because the segments cross thread boundaries the element
+/// trait objects behind `parameter_list`/`result_list` are `Arc<Box<dyn
+/// ElementTrait>>` and `ElementTrait` is `Send + Sync`.
+pub fn compile_segments<T: BCAssigner + Sync>(
+    segments: &[Segment],
+    assigner: &T,
+    threads: usize,
+) -> Vec<(Script, RawWitness)> {
+    if threads <= 1 || segments.len() <= 1 {
+        return segments
+            .iter()
+            .map(|segment| (segment.script(assigner), segment.witness(assigner)))
+            .collect();
+    }
+
+    let threads = threads.min(segments.len());
+    // Ceil-divide so the last partition soaks up the remainder.
+    let chunk_size = segments.len().div_ceil(threads);
+
+    let mut partitions: Vec<Vec<(Script, RawWitness)>> = vec![Vec::new(); threads];
+    std::thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(threads);
+        for (index, chunk) in segments.chunks(chunk_size).enumerate() {
+            let handle = scope.spawn(move || {
+                chunk
+                    .iter()
+                    .map(|segment| (segment.script(assigner), segment.witness(assigner)))
+                    .collect::<Vec<_>>()
+            });
+            handles.push((index, handle));
+        }
+        // Drop each partition back into its original slot to keep input order.
+        for (index, handle) in handles {
+            partitions[index] = handle.join().expect("segment compile worker panicked");
+        }
+    });
+
+    partitions.into_iter().flatten().collect()
+}
@@ -0,0 +1,82 @@
+use super::assigner::BCAssigner;
+use super::segment::Segment;
+use crate::execute_script_with_inputs;
+
+/// Off-chain driver that finds *which* segment a challenger should spend.
+///
+/// Given an operator's full execution trace (the `segments` and the `assigner`
+/// that carries the committed intermediate values), [`locate_disprove`] replays
+/// each non-final branch exactly as the taproot leaf would: it runs
+/// `segment.script(assigner)` against `segment.witness(assigner)` and watches
+/// the `not_equal` check. A non-final segment that leaves `OP_TRUE` on the stack
+/// is the step where the operator's claimed value disagrees with the recomputed
+/// BLAKE3 commitment — the branch that disproves the claim.
+
+/// Where an operator's trace first goes wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisproveLocation {
+    /// Index of the offending segment in the supplied slice.
+    pub index: usize,
+    /// Name of the offending segment, i.e. the taproot branch to spend.
+    pub name: String,
+}
+
+/// Outcome of scanning an operator's trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisproveOutcome {
+    /// A non-final segment failed its `not_equal` check; spend this branch.
+    Disprovable(DisproveLocation),
+    /// No segment fails — the proof is honest.
+    Honest,
+}
+
+/// Replay every non-final segment and return the first one that disproves the
+/// claim, or [`DisproveOutcome::Honest`] when none fails.
+pub fn locate_disprove<T: BCAssigner>(segments: &[Segment], assigner: &T) -> DisproveOutcome {
+    for (index, segment) in segments.iter().enumerate() {
+        if segment.is_final() {
+            continue;
+        }
+
+        let script = segment.script(assigner);
+        let witness = segment.witness(assigner);
+
+        // `not_equal` leaves OP_TRUE exactly when the recomputed commitment
+        // disagrees with the operator's claimed value, which makes the script
+        // succeed with a truthy top element.
+        let res = execute_script_with_inputs(script, witness);
+        if res.success {
+            return DisproveOutcome::Disprovable(DisproveLocation {
+                index,
+                name: segment.name.clone(),
+            });
+        }
+    }
+
+    DisproveOutcome::Honest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{locate_disprove, DisproveOutcome};
+    use crate::chunker::assigner::DummyAssigner;
+    use crate::chunker::elements::DataType::Fq6Data;
+    use crate::chunker::elements::{ElementTrait, Fq6Type};
+    use crate::chunker::segment::Segment;
+    use crate::treepp::*;
+
+    #[test]
+    fn test_locate_disprove_honest_trace() {
+        let mut assigner = DummyAssigner::default();
+
+        let mut a0 = Fq6Type::new(&mut assigner, "a0");
+        a0.fill_with_data(Fq6Data(ark_bn254::Fq6::from(1)));
+
+        let segment = Segment::new(script! {}).add_parameter(&a0).add_result(&a0);
+
+        assert_eq!(
+            locate_disprove(&[segment], &assigner),
+            DisproveOutcome::Honest
+        );
+    }
+}
@@ -0,0 +1,9 @@
+pub mod assigner;
+pub mod backend;
+pub mod cache;
+pub mod common;
+pub mod compile;
+pub mod elements;
+pub mod locator;
+pub mod segment;
+pub mod taproot;
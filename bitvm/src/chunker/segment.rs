@@ -1,21 +1,25 @@
 use super::assigner::BCAssigner;
+use super::backend::{Bn254Backend, ProofBackend};
 use super::common;
 use super::common::*;
 use super::elements::ElementTrait;
 use crate::bn254::utils::Hint;
 use crate::execute_script;
 use crate::treepp::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// Each segment is a branch in the taproot of disprove transaction.
 #[derive(Debug)]
 pub struct Segment {
     pub name: String,
     pub script: Script,
-    pub parameter_list: Vec<Rc<Box<dyn ElementTrait>>>,
-    pub result_list: Vec<Rc<Box<dyn ElementTrait>>>,
+    pub parameter_list: Vec<Arc<Box<dyn ElementTrait>>>,
+    pub result_list: Vec<Arc<Box<dyn ElementTrait>>>,
     pub hints: Vec<Hint>,
     pub final_segment: bool,
+    /// Proof system whose witness identifiers drive the "move data vs. compare
+    /// hash" decision in [`Segment::script`]. Defaults to [`Bn254Backend`].
+    pub backend: Arc<dyn ProofBackend>,
 }
 
 /// After the returned `script` and `witness` are executed together, only `OP_FALSE` left on the stack.
@@ -45,16 +49,23 @@ impl Segment {
             result_list: vec![],
             hints: vec![],
             final_segment: false,
+            backend: Arc::new(Bn254Backend),
         }
     }
 
+    /// Chunk this segment against a different proof system.
+    pub fn with_backend<B: ProofBackend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
     pub fn add_parameter<T: ElementTrait + 'static + Clone>(mut self, x: &T) -> Self {
-        self.parameter_list.push(Rc::new(Box::new(x.clone())));
+        self.parameter_list.push(Arc::new(Box::new(x.clone())));
         self
     }
 
     pub fn add_result<T: ElementTrait + 'static + Clone>(mut self, x: &T) -> Self {
-        self.result_list.push(Rc::new(Box::new(x.clone())));
+        self.result_list.push(Arc::new(Box::new(x.clone())));
         self
     }
 
@@ -88,7 +99,7 @@ impl Segment {
             for parameter in self.parameter_list.iter() {
                 {assigner.locking_script(parameter)} // verify bit commitment
                 // move all original data when verifying the proof
-                if common::PROOF_NAMES.contains(&parameter.id()) {
+                if self.backend.is_proof_element(parameter.id()) {
                     for _ in 0..parameter.as_ref().witness_size() {
                         OP_TOALTSTACK
                     }
@@ -105,7 +116,7 @@ impl Segment {
             let parameter_length = parameter.as_ref().witness_size();
 
             // skip hash when verifying the proof
-            if common::PROOF_NAMES.contains(&parameter.id()) {
+            if self.backend.is_proof_element(parameter.id()) {
                 script = script.push_script(
                     script! {
                         for _ in 0..parameter_length {
@@ -0,0 +1,172 @@
+use bitcoin::absolute::LockTime;
+use bitcoin::key::Secp256k1;
+use bitcoin::secp256k1::XOnlyPublicKey;
+use bitcoin::sighash::{Prevouts, SighashCache};
+use bitcoin::taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder, TaprootSpendInfo};
+use bitcoin::transaction::Version;
+use bitcoin::{
+    OutPoint, Psbt, ScriptBuf, Sequence, TapSighashType, Transaction, TxIn, TxOut, Witness,
+};
+
+use super::assigner::BCAssigner;
+use super::segment::Segment;
+
+/// Each [`Segment`] is a branch in the taproot of the disprove transaction.
+/// This module turns a whole `Vec<Segment>` into the tree itself — one tapleaf
+/// per branch — and then emits a BIP-174 PSBT that spends the committed output
+/// through the chosen branch so an external signer can complete and broadcast
+/// the slash.
+
+/// One resolved tapleaf: the branch's compiled script plus the taproot material
+/// a signer needs to spend through it.
+#[derive(Debug, Clone)]
+pub struct TaprootLeaf {
+    pub index: usize,
+    pub name: String,
+    pub script: ScriptBuf,
+    pub leaf_hash: TapLeafHash,
+    pub control_block: ControlBlock,
+    pub is_final: bool,
+}
+
+/// The assembled taproot of a disprove transaction.
+#[derive(Debug, Clone)]
+pub struct DisproveTaproot {
+    pub spend_info: TaprootSpendInfo,
+    pub leaves: Vec<TaprootLeaf>,
+}
+
+impl DisproveTaproot {
+    /// Assemble the taproot tree from `segments`.
+    ///
+    /// Every `segment.script(assigner)` becomes a tapleaf. Leaves are packed by
+    /// compiled script size via a Huffman tree so that deep branches hold the
+    /// cheap segments and the expensive ones sit on short control-block paths.
+    /// `is_final()` segments omit the `not_equal` comparison and leave the
+    /// true/false result exposed, so they are the terminal slashing leaves: they
+    /// are given the lowest weight and therefore land deepest in the tree.
+    pub fn assemble<T: BCAssigner>(
+        segments: &[Segment],
+        assigner: &T,
+        internal_key: XOnlyPublicKey,
+    ) -> Result<Self, bitcoin::taproot::TaprootBuilderError> {
+        let secp = Secp256k1::verification_only();
+
+        let scripts: Vec<(String, ScriptBuf, bool)> = segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.name.clone(),
+                    segment.script(assigner).compile(),
+                    segment.is_final(),
+                )
+            })
+            .collect();
+
+        // Huffman weight == compiled size for ordinary leaves; final (slashing)
+        // leaves get the minimum weight so they are pushed to the deepest,
+        // terminal positions of the tree.
+        let weighted = scripts.iter().map(|(_, script, is_final)| {
+            let weight = if *is_final {
+                1
+            } else {
+                script.len().max(1).min(u32::MAX as usize) as u32
+            };
+            (weight, script.clone())
+        });
+
+        let spend_info = TaprootBuilder::with_huffman_tree(weighted)?
+            .finalize(&secp, internal_key)
+            .expect("huffman tree is always finalizable");
+
+        let leaves = scripts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, script, is_final))| {
+                let leaf_hash = TapLeafHash::from_script(&script, LeafVersion::TapScript);
+                let control_block = spend_info
+                    .control_block(&(script.clone(), LeafVersion::TapScript))
+                    .expect("leaf is part of the finalized tree");
+                TaprootLeaf {
+                    index,
+                    name,
+                    script,
+                    leaf_hash,
+                    control_block,
+                    is_final,
+                }
+            })
+            .collect();
+
+        Ok(Self { spend_info, leaves })
+    }
+
+    /// Build the unsigned disprove transaction plus its PSBT, spending `prevout`
+    /// at `outpoint` through the script path of the leaf at `leaf_index` and
+    /// paying the slashed value out to `outputs`.
+    ///
+    /// The PSBT input is populated with the spent output, the internal key, the
+    /// merkle root and the chosen leaf script with its control block. The
+    /// taproot script-path sighash of that leaf is not stored in the PSBT; it is
+    /// returned alongside it in [`DisprovePsbt::sighash`], so an external signer
+    /// only has to sign it and add the signature to the witness. `outputs` must be non-empty: a
+    /// transaction with zero outputs is rejected by consensus
+    /// (`bad-txns-vout-empty`), so the caller supplies the payout/burn/anchor
+    /// destination of the slashed funds.
+    pub fn disprove_psbt(
+        &self,
+        leaf_index: usize,
+        outpoint: OutPoint,
+        prevout: TxOut,
+        outputs: Vec<TxOut>,
+    ) -> Result<DisprovePsbt, bitcoin::psbt::Error> {
+        assert!(!outputs.is_empty(), "disprove tx needs at least one output");
+        let leaf = &self.leaves[leaf_index];
+
+        let unsigned_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: outputs,
+        };
+
+        let sighash = SighashCache::new(&unsigned_tx)
+            .taproot_script_spend_signature_hash(
+                0,
+                &Prevouts::All(&[prevout.clone()]),
+                leaf.leaf_hash,
+                TapSighashType::Default,
+            )
+            .expect("single-input script-path sighash");
+
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx)?;
+        let input = &mut psbt.inputs[0];
+        input.witness_utxo = Some(prevout);
+        input.tap_internal_key = Some(self.spend_info.internal_key());
+        input.tap_merkle_root = self.spend_info.merkle_root();
+        input.tap_scripts.insert(
+            leaf.control_block.clone(),
+            (leaf.script.clone(), LeafVersion::TapScript),
+        );
+
+        Ok(DisprovePsbt {
+            psbt,
+            leaf_index,
+            sighash,
+        })
+    }
+}
+
+/// A PSBT for the disprove transaction together with the sighash an external
+/// signer must sign to unlock the chosen branch.
+#[derive(Debug, Clone)]
+pub struct DisprovePsbt {
+    pub psbt: Psbt,
+    pub leaf_index: usize,
+    pub sighash: bitcoin::TapSighash,
+}